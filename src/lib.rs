@@ -9,10 +9,20 @@
 //! with the scope extracted.
 //!
 //! [Scope]'s are defined in the file by prepending a non-whitespaced string that
-//! may only contain ASCII letters, ASCII digits, underscores, and dashes with a ``@``
+//! may only contain ASCII letters, ASCII digits, underscores, dashes, and dots with a ``@``
 //!
 //! Any other content on the line of defined [Scope]'s will be ignored.
 //!
+//! [Scope] names may be hierarchical, using ``.`` to separate atoms in a chain,
+//! following the atom-hierarchy convention used by syntax highlighters (e.g.
+//! ``@dev.debug.verbose``). Extracting a scope also includes every scope nested
+//! beneath it: extracting ``"dev"`` includes lines tagged `@dev`, `@dev.debug`,
+//! and `@dev.debug.verbose`, while extracting ``"dev.debug"`` excludes plain
+//! `@dev` lines. A declared scope is included when extracting a target if the
+//! declared scope equals the target, or the declared scope starts with the
+//! target followed by a ``.``. Dots may not lead, trail, or double up within a
+//! scope name.
+//!
 //! For example,
 //!
 //! ```toml
@@ -71,6 +81,7 @@ use std::fmt;
 
 /// Types of scopes within Quill accessible
 /// in a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Scope<'a> {
     /// Global scope, non-scoped elements in file fall
     /// automatically into this, ALL global scope elements
@@ -104,6 +115,10 @@ pub enum QuillError {
         scope: String,
         line: usize,
         column: usize,
+        /// The offset (within `scope`) and value of the first invalid byte,
+        /// if the name is non-empty. An empty scope name (a bare `@` with no
+        /// name) has no byte to blame, so this is `None` in that case.
+        invalid_byte: Option<(usize, u8)>,
     },
     /// Invalid [Scope] name provided as argument
     InvalidScopeArgument { scope: String },
@@ -116,17 +131,26 @@ impl fmt::Display for QuillError {
                 scope,
                 line,
                 column,
+                invalid_byte,
             } => {
                 write!(
                     f,
-                    "Invalid scope name '{}' at line {}, column {}. Scope names may only contain ASCII letters, ASCII digits, underscores, and dashes.",
+                    "Invalid scope name '{}' at line {}, column {}. Scope names may only contain ASCII letters, ASCII digits, underscores, dashes, and dots (no leading, trailing, or doubled dots).",
                     scope, line, column
-                )
+                )?;
+                if let Some((offset, byte)) = invalid_byte {
+                    write!(
+                        f,
+                        " invalid scope token byte at position {}: {:#04x}",
+                        offset, byte
+                    )?;
+                }
+                Ok(())
             }
             QuillError::InvalidScopeArgument { scope } => {
                 write!(
                     f,
-                    "Invalid scope name '{}'. Scope names may only contain ASCII letters, ASCII digits, underscores, and dashes.",
+                    "Invalid scope name '{}'. Scope names may only contain ASCII letters, ASCII digits, underscores, dashes, and dots (no leading, trailing, or doubled dots).",
                     scope
                 )
             }
@@ -136,14 +160,172 @@ impl fmt::Display for QuillError {
 
 impl std::error::Error for QuillError {}
 
+/// Finds the offset and value of the first invalid byte within a candidate
+/// scope `name`, if any. A byte is invalid if it isn't an ASCII letter,
+/// ASCII digit, underscore, dash, or dot, or if it's a dot that would leave
+/// the name with a leading, trailing, or doubled dot (dots separate atoms in
+/// a hierarchical scope name, so they may not be empty on either side).
+fn find_invalid_scope_byte(name: &str) -> Option<(usize, u8)> {
+    let bytes = name.as_bytes();
+
+    bytes.iter().enumerate().find_map(|(i, &byte)| {
+        let is_allowed = byte.is_ascii_alphanumeric() || byte == b'_' || byte == b'-' || byte == b'.';
+        let is_bad_dot =
+            byte == b'.' && (i == 0 || i == bytes.len() - 1 || bytes.get(i + 1) == Some(&b'.'));
+
+        (!is_allowed || is_bad_dot).then_some((i, byte))
+    })
+}
+
 /// Validates that a name matches the required [Scope]
 /// name as per spec, which is that it only contains
-/// ASCII letters, ASCII digits, underscores, and dashes
+/// ASCII letters, ASCII digits, underscores, dashes, and dots,
+/// with dots forbidden from leading, trailing, or doubling up
+/// (since they separate atoms in a hierarchical scope name)
 fn is_valid_scope_name(name: &str) -> bool {
-    !name.is_empty()
-        && name
-            .chars()
-            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    !name.is_empty() && find_invalid_scope_byte(name).is_none()
+}
+
+/// Validates a single `@`-prefixed `token` found on `line`, returning the
+/// [QuillError] it produces if invalid, or `None` if it's not a scope token
+/// (doesn't start with `@`) or is a valid one. Shared by [ScopeReader]'s
+/// iteration (which stops at the first invalid token per line) and
+/// [validate] (which uses it to collect every invalid token on a line
+/// instead).
+fn validate_scope_token(token: &str, line: &str, line_number: usize) -> Option<QuillError> {
+    let scope_name = token.strip_prefix('@')?;
+    let column = line.find('@').unwrap_or(0) + 1;
+
+    if scope_name.is_empty() {
+        return Some(QuillError::InvalidScopeName {
+            scope: scope_name.to_string(),
+            line: line_number,
+            column,
+            invalid_byte: None,
+        });
+    }
+
+    find_invalid_scope_byte(scope_name).map(|invalid_byte| QuillError::InvalidScopeName {
+        scope: scope_name.to_string(),
+        line: line_number,
+        column,
+        invalid_byte: Some(invalid_byte),
+    })
+}
+
+/// Checks whether a declared [Scope] name falls under the given target,
+/// following the atom-hierarchy convention: a declared scope is included
+/// when extracting `target` if `declared == target`, or if `declared` is
+/// nested under `target` (i.e. `target`'s dot-separated atoms are a prefix
+/// of `declared`'s atoms). This lets extracting `"dev"` also pull in
+/// `"dev.debug"` and `"dev.debug.verbose"`.
+fn scope_matches_target(declared: &str, target: &str) -> bool {
+    if declared == target {
+        return true;
+    }
+
+    let declared_atoms: Vec<&str> = declared.split('.').collect();
+    let target_atoms: Vec<&str> = target.split('.').collect();
+
+    target_atoms.len() < declared_atoms.len() && declared_atoms[..target_atoms.len()] == target_atoms[..]
+}
+
+/// A single line yielded by [ScopeReader], borrowing its content directly
+/// from the source without copying.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScopeLine<'a> {
+    /// The 1-based line number within the source
+    pub line_number: usize,
+    /// The raw line content, exactly as it appears in the source
+    pub content: &'a str,
+    /// The [Scope] names active for this line, i.e. the most recently
+    /// declared `@scope` tokens, or `["global"]` before any declaration.
+    ///
+    /// This is `Rc<[&'a str]>` rather than a plain `&'a [&'a str]`: a
+    /// [ScopeReader] item can't borrow from `&mut self` across calls to
+    /// `next`, so the slice has to be owned by the yielded value, and an
+    /// `Rc` lets unchanged lines share it with a refcount bump instead of
+    /// reallocating a `Vec` on every line. This is a deliberate deviation
+    /// from a plain borrowed slice, not an oversight.
+    pub active_scopes: std::rc::Rc<[&'a str]>,
+}
+
+/// A lazy iterator over the lines of a Quill-annotated ``TOML`` file,
+/// tracking the active [Scope] set as it goes and borrowing every line's
+/// content directly from the input with no copying.
+///
+/// Unlike [extract_scope], which allocates a [String] the size of the whole
+/// input up front, a [ScopeReader] yields one [ScopeLine] at a time, so
+/// callers can filter by their own predicate and `collect` only what they
+/// need, supporting very large files and multiple scope passes without
+/// re-parsing. The active scope set is only reallocated when a new `@scope`
+/// declaration is encountered; every other line reuses it via a cheap
+/// [Rc](std::rc::Rc) clone. Construct one with [scope_lines].
+pub struct ScopeReader<'a> {
+    lines: std::str::Lines<'a>,
+    current_scopes: std::rc::Rc<[&'a str]>,
+    line_number: usize,
+}
+
+impl<'a> Iterator for ScopeReader<'a> {
+    type Item = Result<ScopeLine<'a>, QuillError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let line = self.lines.next()?;
+        self.line_number += 1;
+        let trimmed = line.trim_start();
+
+        // Check if this line contains scope declarations
+        if trimmed.starts_with('@') {
+            // Extract all scopes from this line
+            let mut scopes: Vec<&str> = Vec::new();
+
+            for token in trimmed.split_whitespace() {
+                if let Some(scope_name) = token.strip_prefix('@') {
+                    // Validate scope name; a malformed token like `@dev@bad`
+                    // or a bare `@` with no name is reported rather than
+                    // silently mis-split
+                    if let Some(err) = validate_scope_token(token, line, self.line_number) {
+                        return Some(Err(err));
+                    }
+
+                    scopes.push(scope_name);
+                }
+            }
+
+            if !scopes.is_empty() {
+                self.current_scopes = std::rc::Rc::from(scopes);
+            }
+        }
+
+        Some(Ok(ScopeLine {
+            line_number: self.line_number,
+            content: line,
+            active_scopes: std::rc::Rc::clone(&self.current_scopes),
+        }))
+    }
+}
+
+/// Constructs a lazy [ScopeReader] over `toml`'s lines.
+///
+/// # Example
+///
+/// ```
+/// use quill::scope_lines;
+///
+/// let toml = "title = \"App\"\n\n@dev\ndebug = true";
+/// let lines: Vec<_> = scope_lines(toml).collect::<Result<_, _>>().unwrap();
+///
+/// assert_eq!(lines.len(), 4);
+/// assert_eq!(lines[3].content, "debug = true");
+/// assert_eq!(lines[3].active_scopes.as_ref(), ["dev"]);
+/// ```
+pub fn scope_lines(toml: &str) -> ScopeReader<'_> {
+    ScopeReader {
+        lines: toml.lines(),
+        current_scopes: std::rc::Rc::from(vec![Scope::Global.into()]),
+        line_number: 0,
+    }
 }
 
 /// Extracts the provided [Scope] from the provided
@@ -195,6 +377,35 @@ fn is_valid_scope_name(name: &str) -> bool {
 ///
 /// do_tests = true"#);
 /// ```
+///
+/// # Hierarchical Example
+///
+/// Extracting a parent scope also pulls in everything nested beneath it:
+///
+/// ```
+/// use quill::{extract_scope, Scope};
+///
+/// let toml = r#"
+/// @dev
+/// debug = true
+///
+/// @dev.debug.verbose
+/// trace = true
+///
+/// @prod
+/// optimized = true"#;
+///
+/// let dev_config = extract_scope(toml, Scope::DefinedScope("dev")).unwrap();
+/// assert_eq!(dev_config, r#"
+///
+/// debug = true
+///
+///
+/// trace = true
+///
+///
+/// "#);
+/// ```
 pub fn extract_scope<'a, 'b, T: Into<&'a str>>(
     toml_str: T,
     scope: Scope<'b>,
@@ -212,58 +423,169 @@ pub fn extract_scope<'a, 'b, T: Into<&'a str>>(
 
     // String that will be returned at end on success.
     let mut result = String::with_capacity(toml_str.len());
-    let mut lines = toml_str.lines();
-    let mut current_scopes: Vec<&str> = vec![Scope::Global.into()];
-    let mut include_content = true;
-    let mut line_number = 0;
 
-    while let Some(line) = lines.next() {
-        line_number += 1;
-        let trimmed = line.trim_start();
+    for scoped_line in scope_lines(toml_str) {
+        let scoped_line = scoped_line?;
 
-        // Check if this line contains scope declarations
-        if trimmed.starts_with('@') {
-            // Extract all scopes from this line
-            let mut scopes: Vec<&str> = Vec::new();
+        // Scope declaration lines are always blanked out, regardless of
+        // whether the scope they declare matches our target, to preserve
+        // line numbers
+        let is_declaration = scoped_line.content.trim_start().starts_with('@');
 
-            for token in trimmed.split_whitespace() {
-                if token.starts_with('@') {
-                    let scope_name = &token[1..];
-
-                    // Validate scope name
-                    if !is_valid_scope_name(scope_name) {
-                        // Calculate column number (position of @ symbol)
-                        let column = line.find('@').unwrap_or(0) + 1;
-                        return Err(QuillError::InvalidScopeName {
-                            scope: scope_name.to_string(),
-                            line: line_number,
-                            column,
-                        });
-                    }
+        if !is_declaration {
+            // Check if any of the declared scopes match our target
+            let include_content = scoped_line
+                .active_scopes
+                .iter()
+                .any(|declared| scope_matches_target(declared, target_scope))
+                || scoped_line.active_scopes.contains(&Scope::Global.into())
+                || target_scope.eq(Scope::Global.into());
 
-                    scopes.push(scope_name);
-                }
+            if include_content {
+                result.push_str(scoped_line.content);
             }
+        }
+        result.push('\n');
+    }
 
-            if !scopes.is_empty() {
-                current_scopes = scopes;
-                // Check if any of the declared scopes match our target
-                include_content = current_scopes.contains(&target_scope)
-                    || current_scopes.contains(&Scope::Global.into())
-                    || target_scope.eq(Scope::Global.into());
-
-                // Add empty line to preserve line numbers
-                result.push('\n');
-                continue;
-            }
+    // Remove the trailing newline if the original didn't have one
+    if !toml_str.ends_with('\n') && result.ends_with('\n') {
+        result.pop();
+    }
+
+    Ok(result)
+}
+
+/// How a set of target [Scope]'s combine when extracting with [extract_scopes]
+pub enum Combine {
+    /// Include a line if its declared scopes intersect any of the requested
+    /// targets. This is the set-union behavior [extract_scope] uses for a
+    /// single target, generalized to many, and it's hierarchy-aware: a line
+    /// declared `@dev.debug` matches a request for `dev`.
+    Any,
+    /// Include a line only if every requested target is present among the
+    /// line's declared scopes, i.e. the requested set is a subset of the
+    /// declared set. See [scope_satisfies].
+    ///
+    /// Unlike [Combine::Any], this is a literal subset check, not
+    /// hierarchy-aware: a line declared `@dev.debug` does **not** satisfy a
+    /// request for `dev` under `All`, since `"dev.debug"` never literally
+    /// appears as `"dev"` in the declared set.
+    All,
+}
+
+/// Checks whether `required` is a subset of `declared`, i.e. whether every
+/// scope token in `required` also appears in `declared`. This mirrors OAuth2
+/// scope semantics where a resource is satisfied only if all required scope
+/// tokens are present: `A <= B` iff every token of `A` appears in `B`. An
+/// empty `required` is vacuously satisfied by any `declared`.
+///
+/// # Example
+///
+/// ```
+/// use quill::scope_satisfies;
+///
+/// assert!(scope_satisfies(&["dev", "test"], &["dev"]));
+/// assert!(!scope_satisfies(&["dev"], &["dev", "test"]));
+/// ```
+pub fn scope_satisfies(declared: &[&str], required: &[&str]) -> bool {
+    required.iter().all(|token| declared.contains(token))
+}
+
+/// Extracts several [Scope]'s from the provided toml str at once, generalizing
+/// [extract_scope] to many targets combined by set semantics.
+///
+/// Under [Combine::Any], a line is included if its declared scopes intersect
+/// any of the requested `scopes` (today's [extract_scope] behavior,
+/// generalized to many targets). Under [Combine::All], a line is included
+/// only when every requested scope is present on that line, per
+/// [scope_satisfies] - a line tagged `@dev @test` is emitted for a request of
+/// `{dev, test}` or `{dev}`, but a line tagged only `@dev` is not emitted for
+/// a request of `{dev, test}`.
+///
+/// See [Combine] for a note on hierarchy: `Any` matches `dev.debug` against a
+/// `dev` request, but `All`'s literal subset check does not.
+///
+/// An empty `scopes` slice is vacuously satisfied under [Combine::All] (there
+/// are no required tokens left unmet), so `extract_scopes(toml, &[],
+/// Combine::All)` keeps every content line, with only the `@scope`
+/// declaration lines blanked - pass at least one target if that's not what
+/// you want. Under [Combine::Any] an empty slice intersects nothing, so only
+/// global content is kept.
+///
+/// Global scope content continues to always satisfy, regardless of `combine`.
+/// A [Scope::Global] target keeps every content line under [Combine::Any], as
+/// it does for [extract_scope]; under [Combine::All] the same holds when every
+/// requested target is [Scope::Global].
+///
+/// Read more about [Scope]'s in the crate level documentation.
+///
+/// # Example
+///
+/// ```
+/// use quill::{extract_scopes, Scope, Combine};
+///
+/// let toml = r#"
+/// @dev
+/// debug = true
+///
+/// @dev @test
+/// extra_checks = true
+///
+/// @test
+/// only_test = true"#;
+///
+/// let targets = [Scope::DefinedScope("dev"), Scope::DefinedScope("test")];
+///
+/// let all_config = extract_scopes(toml, &targets, Combine::All).unwrap();
+/// assert_eq!(all_config, "\n\n\n\n\nextra_checks = true\n\n\n");
+/// ```
+pub fn extract_scopes<'a, 'b, T: Into<&'a str>>(
+    toml_str: T,
+    scopes: &[Scope<'b>],
+    combine: Combine,
+) -> Result<String, QuillError> {
+    // Extract args into string
+    let toml_str = toml_str.into();
+    let global: &str = Scope::Global.into();
+    let target_scopes: Vec<&str> = scopes.iter().map(|scope| (*scope).into()).collect();
+
+    // Validate every target scope name (unless it's "global")
+    for target_scope in &target_scopes {
+        if target_scope.ne(&global) && !is_valid_scope_name(target_scope) {
+            return Err(QuillError::InvalidScopeArgument {
+                scope: target_scope.to_string(),
+            });
         }
+    }
+
+    // String that will be returned at end on success.
+    let mut result = String::with_capacity(toml_str.len());
+
+    for scoped_line in scope_lines(toml_str) {
+        let scoped_line = scoped_line?;
+        let is_declaration = scoped_line.content.trim_start().starts_with('@');
+
+        if !is_declaration {
+            // Check if the declared scopes satisfy our targets, per `combine`
+            let include_content = scoped_line.active_scopes.contains(&global)
+                || match combine {
+                    Combine::Any => target_scopes.iter().any(|target| {
+                        target.eq(&global)
+                            || scoped_line
+                                .active_scopes
+                                .iter()
+                                .any(|declared| scope_matches_target(declared, target))
+                    }),
+                    Combine::All => {
+                        target_scopes.iter().all(|target| target.eq(&global))
+                            || scope_satisfies(&scoped_line.active_scopes, &target_scopes)
+                    }
+                };
 
-        // Include or exclude content based on current scope
-        if include_content {
-            result.push_str(line);
-        } else {
-            // Replace content with empty space to preserve line numbers
-            // Keep the newline structure
+            if include_content {
+                result.push_str(scoped_line.content);
+            }
         }
         result.push('\n');
     }
@@ -275,3 +597,133 @@ pub fn extract_scope<'a, 'b, T: Into<&'a str>>(
 
     Ok(result)
 }
+
+/// Where a single [Scope] lives within a source file: the line ranges its
+/// content spans, and the lines on which it is declared via `@scope`
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ScopeInfo {
+    /// Line ranges of content belonging to this scope, merged across every
+    /// declaration of it in the file
+    pub content_ranges: Vec<std::ops::RangeInclusive<usize>>,
+    /// Line numbers on which this scope is declared, i.e. every `@scope` line
+    pub declared_at: Vec<usize>,
+}
+
+/// A map from [Scope] name to where it lives in a source file, as produced
+/// by [list_scopes]
+#[derive(Debug, Clone, Default)]
+pub struct ScopeMap {
+    /// The scopes encountered in the file, keyed by name, including `global`
+    pub scopes: std::collections::BTreeMap<String, ScopeInfo>,
+}
+
+/// Scans `toml` and reports, for every [Scope] name encountered (including
+/// `global`), the line ranges of content that belong to it and the lines
+/// where it is declared.
+///
+/// This lets tooling answer "which scopes does this file define, and which
+/// lines does `prod` own" without extracting each scope separately, powering
+/// editor integrations or lint passes. Every declaration name is validated,
+/// surfacing [QuillError::InvalidScopeName] with the same line/column info
+/// [extract_scope] would.
+///
+/// # Example
+///
+/// ```
+/// use quill::list_scopes;
+///
+/// let toml = "title = \"App\"\n\n@dev\ndebug = true\nverbose = true\n\n@prod\noptimized = true";
+///
+/// let map = list_scopes(toml).unwrap();
+///
+/// let dev = map.scopes.get("dev").unwrap();
+/// assert_eq!(dev.declared_at, vec![3]);
+/// assert_eq!(dev.content_ranges, vec![4..=6]);
+///
+/// let global = map.scopes.get("global").unwrap();
+/// assert_eq!(global.content_ranges, vec![1..=2]);
+/// ```
+pub fn list_scopes(toml: &str) -> Result<ScopeMap, QuillError> {
+    let mut scopes: std::collections::BTreeMap<String, ScopeInfo> = std::collections::BTreeMap::new();
+    let global: &str = Scope::Global.into();
+    scopes.entry(global.to_string()).or_default();
+
+    for scoped_line in scope_lines(toml) {
+        let scoped_line = scoped_line?;
+        let is_declaration = scoped_line.content.trim_start().starts_with('@');
+
+        // A line may repeat the same scope token (e.g. `@dev @dev`); dedup
+        // before updating the map so each scope gets a single declared-at
+        // entry and content range per line, not one per repeated token
+        let unique_scopes: std::collections::HashSet<&str> =
+            scoped_line.active_scopes.iter().copied().collect();
+
+        if is_declaration {
+            for &name in &unique_scopes {
+                scopes
+                    .entry(name.to_string())
+                    .or_default()
+                    .declared_at
+                    .push(scoped_line.line_number);
+            }
+            continue;
+        }
+
+        for &name in &unique_scopes {
+            let info = scopes.entry(name.to_string()).or_default();
+
+            match info.content_ranges.last_mut() {
+                Some(range) if *range.end() + 1 == scoped_line.line_number => {
+                    *range = *range.start()..=scoped_line.line_number;
+                }
+                _ => info
+                    .content_ranges
+                    .push(scoped_line.line_number..=scoped_line.line_number),
+            }
+        }
+    }
+
+    Ok(ScopeMap { scopes })
+}
+
+/// Validates every `@scope` declaration in `toml` and collects all the
+/// invalid ones, rather than stopping at the first, as [extract_scope] and
+/// friends do. This lets users fixing a config see every problem in one
+/// pass instead of re-running after each fix - including every malformed
+/// token on a single line (e.g. `@bad! @worse!` reports both, not just the
+/// first).
+///
+/// # Example
+///
+/// ```
+/// use quill::validate;
+///
+/// let toml = "@dev!\ntitle = 1\n@.bad\nx = 2\n@worse! @bad!";
+///
+/// let errors = validate(toml).unwrap_err();
+/// assert_eq!(errors.len(), 4);
+/// ```
+pub fn validate(toml: &str) -> Result<(), Vec<QuillError>> {
+    let mut errors = Vec::new();
+
+    for (line_number, line) in toml.lines().enumerate() {
+        let line_number = line_number + 1;
+        let trimmed = line.trim_start();
+
+        if !trimmed.starts_with('@') {
+            continue;
+        }
+
+        errors.extend(
+            trimmed
+                .split_whitespace()
+                .filter_map(|token| validate_scope_token(token, line, line_number)),
+        );
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}